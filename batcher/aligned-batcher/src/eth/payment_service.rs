@@ -0,0 +1,38 @@
+use ethers::prelude::*;
+
+abigen!(
+    BatcherPaymentServiceContract,
+    "abi/BatcherPaymentService.json"
+);
+
+pub type SignerMiddlewareT = SignerMiddleware<Provider<Http>, LocalWallet>;
+pub type BatcherPaymentService = BatcherPaymentServiceContract<SignerMiddlewareT>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct CreateNewTaskFeeParams {
+    pub fee_for_aggregator: U256,
+    pub fee_per_proof: U256,
+    pub respond_to_task_fee_limit: U256,
+    pub gas_price: U256,
+    /// Floor for the EIP-1559 `maxPriorityFeePerGas` computed from `eth_feeHistory`, so the
+    /// batcher never submits a tip low enough to be deprioritized.
+    pub priority_fee_floor: U256,
+}
+
+impl CreateNewTaskFeeParams {
+    pub fn new(
+        fee_for_aggregator: U256,
+        fee_per_proof: U256,
+        respond_to_task_fee_limit: U256,
+        gas_price: U256,
+        priority_fee_floor: U256,
+    ) -> Self {
+        Self {
+            fee_for_aggregator,
+            fee_per_proof,
+            respond_to_task_fee_limit,
+            gas_price,
+            priority_fee_floor,
+        }
+    }
+}
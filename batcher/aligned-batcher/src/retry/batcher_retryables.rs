@@ -1,163 +1,444 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
 use ethers::prelude::*;
+use ethers::providers::JsonRpcClient;
+use futures::future::join_all;
 use log::{info, warn};
 use tokio::time::timeout;
 
 use crate::{
-    eth::{
-        payment_service::{BatcherPaymentService, CreateNewTaskFeeParams, SignerMiddlewareT},
-        utils::get_current_nonce,
-    },
+    eth::payment_service::{BatcherPaymentService, CreateNewTaskFeeParams, SignerMiddlewareT},
     retry::RetryError,
     types::errors::{BatcherError, TransactionSendError},
 };
 
+/// Number of trailing blocks sampled by `eth_feeHistory` when estimating EIP-1559 fees.
+const FEE_HISTORY_BLOCK_COUNT: u64 = 20;
+/// Reward percentile requested from `eth_feeHistory` (the median tip paid by included txs).
+const FEE_HISTORY_REWARD_PERCENTILE: f64 = 50.0;
+
+/// Caches the batcher signer's next nonce in memory so sends don't round-trip through
+/// `eth_getTransactionCount` and two in-flight sends can't reserve the same nonce.
+pub struct NonceManager {
+    next_nonce: AtomicU64,
+}
+
+impl NonceManager {
+    /// Seeds the manager from the chain's current transaction count for `addr`.
+    pub async fn new<P: JsonRpcClient>(
+        provider_set: &ProviderSet<Provider<P>>,
+        addr: Address,
+    ) -> Result<Self, RetryError<ProviderError>> {
+        let chain_nonce = get_current_nonce_retryable(provider_set, addr).await?;
+        Ok(Self {
+            next_nonce: AtomicU64::new(chain_nonce.as_u64()),
+        })
+    }
+
+    /// Atomically hands out the next unused nonce.
+    pub fn reserve_next(&self) -> U256 {
+        U256::from(self.next_nonce.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Re-syncs the cached nonce from the chain.
+    pub async fn resync<P: JsonRpcClient>(
+        &self,
+        provider_set: &ProviderSet<Provider<P>>,
+        addr: Address,
+    ) -> Result<(), RetryError<ProviderError>> {
+        let chain_nonce = get_current_nonce_retryable(provider_set, addr).await?;
+        self.next_nonce.store(chain_nonce.as_u64(), Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+/// An ordered set of equivalent RPC endpoints, replacing the old primary/fallback pair.
+pub struct ProviderSet<T> {
+    providers: Vec<T>,
+    quorum: usize,
+}
+
+impl<T> ProviderSet<T> {
+    pub fn new(providers: Vec<T>, quorum: usize) -> Self {
+        assert!(!providers.is_empty(), "ProviderSet requires at least one provider");
+        Self {
+            quorum: quorum.clamp(1, providers.len()),
+            providers,
+        }
+    }
+
+    pub fn providers(&self) -> &[T] {
+        &self.providers
+    }
+}
+
+/// Queries every provider concurrently and returns the value at least `quorum` agree on.
+async fn quorum_read<T, F, Fut, V, E>(
+    provider_set: &ProviderSet<T>,
+    call: F,
+    no_quorum_error: impl FnOnce() -> E,
+) -> Result<V, RetryError<E>>
+where
+    F: Fn(&T) -> Fut,
+    Fut: std::future::Future<Output = Result<V, E>>,
+    V: Eq + Hash,
+{
+    let results = join_all(provider_set.providers.iter().map(call)).await;
+
+    let mut tally: HashMap<V, usize> = HashMap::new();
+    for value in results.into_iter().flatten() {
+        *tally.entry(value).or_insert(0) += 1;
+    }
+
+    tally
+        .into_iter()
+        .find(|(_, count)| *count >= provider_set.quorum)
+        .map(|(value, _)| value)
+        .ok_or_else(|| {
+            warn!(
+                "Quorum of {} not reached among {} providers",
+                provider_set.quorum,
+                provider_set.providers.len()
+            );
+            RetryError::Transient(no_quorum_error())
+        })
+}
+
+/// Calls `send` against each provider in order, returning the first success; stops early without
+/// trying the rest once `stop_early` says an error isn't worth retrying elsewhere.
+async fn try_until_success<T, F, Fut, V, E>(
+    provider_set: &ProviderSet<T>,
+    send: F,
+    stop_early: impl Fn(&E) -> bool,
+) -> Result<V, E>
+where
+    F: Fn(&T) -> Fut,
+    Fut: std::future::Future<Output = Result<V, E>>,
+{
+    let mut last_err = None;
+    for provider in &provider_set.providers {
+        match send(provider).await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                let stop = stop_early(&e);
+                last_err = Some(e);
+                if stop {
+                    break;
+                }
+            }
+        }
+    }
+    Err(last_err.expect("ProviderSet is never empty"))
+}
+
+/// Calls `send` against each provider in order, returning the first success.
+async fn first_success<T, F, Fut, V, E>(provider_set: &ProviderSet<T>, send: F) -> Result<V, E>
+where
+    F: Fn(&T) -> Fut,
+    Fut: std::future::Future<Output = Result<V, E>>,
+{
+    try_until_success(provider_set, send, |_| false).await
+}
+
+/// Like `first_success`, but stops at the first revert instead of resubmitting the same doomed
+/// call to the rest of the provider list.
+async fn first_contract_success<T, F, Fut, M, V>(
+    provider_set: &ProviderSet<T>,
+    call: F,
+) -> Result<V, ContractError<M>>
+where
+    F: Fn(&T) -> Fut,
+    Fut: std::future::Future<Output = Result<V, ContractError<M>>>,
+    M: Middleware,
+{
+    try_until_success(provider_set, call, |e| matches!(e, ContractError::Revert(_))).await
+}
+
 pub async fn get_user_balance_retryable(
-    payment_service: &BatcherPaymentService,
-    payment_service_fallback: &BatcherPaymentService,
+    provider_set: &ProviderSet<BatcherPaymentService>,
     addr: &Address,
 ) -> Result<U256, RetryError<String>> {
-    if let Ok(balance) = payment_service.user_balances(*addr).call().await {
-        return Ok(balance);
-    };
-
-    payment_service_fallback
-        .user_balances(*addr)
-        .call()
-        .await
-        .map_err(|e| {
-            warn!("Failed to get balance for address {:?}. Error: {e}", addr);
-            RetryError::Transient(e.to_string())
-        })
+    let addr = *addr;
+    quorum_read(
+        provider_set,
+        move |payment_service| async move {
+            payment_service
+                .user_balances(addr)
+                .call()
+                .await
+                .map_err(|e| e.to_string())
+        },
+        || {
+            warn!("Failed to get balance for address {:?}", addr);
+            "failed to reach quorum on user balance".to_string()
+        },
+    )
+    .await
 }
 
 pub async fn get_user_nonce_from_ethereum_retryable(
-    payment_service: &BatcherPaymentService,
-    payment_service_fallback: &BatcherPaymentService,
+    provider_set: &ProviderSet<BatcherPaymentService>,
     addr: Address,
 ) -> Result<U256, RetryError<String>> {
-    if let Ok(nonce) = payment_service.user_nonces(addr).call().await {
-        return Ok(nonce);
-    }
-    payment_service_fallback
-        .user_nonces(addr)
-        .call()
-        .await
-        .map_err(|e| {
-            warn!("Error getting user nonce: {e}");
-            RetryError::Transient(e.to_string())
-        })
+    quorum_read(
+        provider_set,
+        move |payment_service| async move {
+            payment_service
+                .user_nonces(addr)
+                .call()
+                .await
+                .map_err(|e| e.to_string())
+        },
+        || "failed to reach quorum on user nonce".to_string(),
+    )
+    .await
 }
 
-pub async fn get_current_nonce_retryable(
-    eth_http_provider: &Provider<Http>,
-    eth_http_provider_fallback: &Provider<Http>,
+pub async fn get_current_nonce_retryable<P: JsonRpcClient>(
+    provider_set: &ProviderSet<Provider<P>>,
     addr: Address,
 ) -> Result<U256, RetryError<ProviderError>> {
-    match eth_http_provider.get_transaction_count(addr, None).await {
-        Ok(current_nonce) => Ok(current_nonce),
-        Err(_) => eth_http_provider_fallback
-            .get_transaction_count(addr, None)
-            .await
-            .map_err(|e| {
-                warn!("Error getting user nonce: {e}");
-                RetryError::Transient(e)
-            }),
-    }
+    quorum_read(
+        provider_set,
+        move |eth_http_provider| async move { eth_http_provider.get_transaction_count(addr, None).await },
+        || ProviderError::CustomError("failed to reach quorum on account nonce".to_string()),
+    )
+    .await
 }
 
 pub async fn user_balance_is_unlocked_retryable(
-    payment_service: &BatcherPaymentService,
-    payment_service_fallback: &BatcherPaymentService,
+    provider_set: &ProviderSet<BatcherPaymentService>,
     addr: &Address,
 ) -> Result<bool, RetryError<()>> {
-    if let Ok(unlock_block) = payment_service.user_unlock_block(*addr).call().await {
-        return Ok(unlock_block != U256::zero());
+    let addr = *addr;
+    quorum_read(
+        provider_set,
+        move |payment_service| async move {
+            payment_service
+                .user_unlock_block(addr)
+                .call()
+                .await
+                .map(|unlock_block| unlock_block != U256::zero())
+                .map_err(|_| ())
+        },
+        || {
+            warn!("Failed to get user locking state {:?}", addr);
+        },
+    )
+    .await
+}
+
+pub async fn get_gas_price_retryable<P: JsonRpcClient>(
+    provider_set: &ProviderSet<Provider<P>>,
+) -> Result<U256, RetryError<ProviderError>> {
+    first_success(provider_set, |eth_http_provider| {
+        eth_http_provider.get_gas_price()
+    })
+    .await
+    .map_err(|e| {
+        warn!("Failed to get gas price from every provider: {e:?}");
+        RetryError::Transient(e)
+    })
+}
+
+/// Estimates EIP-1559 fee parameters from the last [`FEE_HISTORY_BLOCK_COUNT`] blocks. Returns
+/// `Ok(None)` when the node's fee history comes back empty, signaling a fall back to legacy
+/// pricing.
+pub async fn estimate_eip1559_fees_retryable(
+    provider_set: &ProviderSet<BatcherPaymentService>,
+    priority_fee_floor: U256,
+) -> Result<Option<(U256, U256)>, RetryError<BatcherError>> {
+    let fee_history = first_success(provider_set, |payment_service| {
+        payment_service.client().fee_history(
+            U256::from(FEE_HISTORY_BLOCK_COUNT),
+            BlockNumber::Latest,
+            &[FEE_HISTORY_REWARD_PERCENTILE],
+        )
+    })
+    .await
+    .map_err(|e| {
+        warn!("Failed to get fee history from every provider: {e}");
+        RetryError::Transient(BatcherError::TransactionSendError(
+            TransactionSendError::Generic(e.to_string()),
+        ))
+    })?;
+
+    let Some(base_fee_per_gas) = fee_history.base_fee_per_gas.last().copied() else {
+        info!("Node returned an empty fee history, falling back to legacy gas pricing");
+        return Ok(None);
+    };
+
+    let reward_samples: Vec<U256> = fee_history
+        .reward
+        .into_iter()
+        .filter_map(|block_rewards| block_rewards.first().copied())
+        .collect();
+
+    let max_priority_fee_per_gas = median_priority_fee(reward_samples, priority_fee_floor);
+    let max_fee_per_gas = base_fee_per_gas.saturating_mul(2.into()) + max_priority_fee_per_gas;
+
+    Ok(Some((max_fee_per_gas, max_priority_fee_per_gas)))
+}
+
+/// Median of the per-block reward samples, clamped to `priority_fee_floor`.
+fn median_priority_fee(mut reward_samples: Vec<U256>, priority_fee_floor: U256) -> U256 {
+    reward_samples.sort();
+    reward_samples
+        .get(reward_samples.len() / 2)
+        .copied()
+        .unwrap_or(priority_fee_floor)
+        .max(priority_fee_floor)
+}
+
+/// Sets `maxFeePerGas`/`maxPriorityFeePerGas` on a pending contract call as an EIP-1559 tx.
+fn apply_eip1559_fees<M, D>(
+    call: &mut ContractCall<M, D>,
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+) {
+    call.tx.set_transaction_type(Some(2.into()));
+    if let Some(eip1559_tx) = call.tx.as_eip1559_mut() {
+        eip1559_tx.max_fee_per_gas = Some(max_fee_per_gas);
+        eip1559_tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
     }
-    if let Ok(unlock_block) = payment_service_fallback
-        .user_unlock_block(*addr)
-        .call()
-        .await
-    {
-        return Ok(unlock_block != U256::zero());
+}
+
+/// Applies `legacy_gas_price` to `tx`, promoting it from type-0 to type-1 first if an access list
+/// will be attached — `TypedTransaction::set_access_list` is a no-op on the `Legacy` variant, so
+/// without this promotion a prewarmed access list would silently be dropped.
+fn apply_legacy_fee_and_prepare_access_list(
+    tx: &mut TypedTransaction,
+    legacy_gas_price: U256,
+    has_access_list: bool,
+) {
+    tx.set_gas_price(legacy_gas_price);
+    if has_access_list {
+        tx.set_transaction_type(Some(1.into()));
     }
-    warn!("Failed to get user locking state {:?}", addr);
-    Err(RetryError::Transient(()))
 }
 
-pub async fn get_gas_price_retryable(
-    eth_http_provider: &Provider<Http>,
-    eth_http_provider_fallback: &Provider<Http>,
-) -> Result<U256, RetryError<ProviderError>> {
-    match eth_http_provider.get_gas_price().await {
-        Ok(gas_price) => Ok(gas_price),
-        Err(_) => eth_http_provider_fallback
-            .get_gas_price()
-            .await
-            .map_err(|e| {
-                warn!("Failed to get fallback gas price: {e:?}");
-                RetryError::Transient(e)
-            }),
+/// Applies the chosen fee model to `call`, then attaches `access_list` if present.
+fn apply_fees_and_access_list<M, D>(
+    call: &mut ContractCall<M, D>,
+    fees: Option<(U256, U256)>,
+    legacy_gas_price: U256,
+    access_list: Option<AccessList>,
+) {
+    match fees {
+        Some((max_fee_per_gas, max_priority_fee_per_gas)) => {
+            apply_eip1559_fees(call, max_fee_per_gas, max_priority_fee_per_gas)
+        }
+        None => {
+            apply_legacy_fee_and_prepare_access_list(
+                &mut call.tx,
+                legacy_gas_price,
+                access_list.is_some(),
+            )
+        }
     }
+    if let Some(access_list) = access_list {
+        call.tx.set_access_list(access_list);
+    }
+}
+
+/// Requests the EIP-2930 access list for `tx` via `eth_createAccessList`. Returns `None` when no
+/// provider supports it, so the caller can submit without one.
+async fn prewarm_access_list_retryable(
+    provider_set: &ProviderSet<BatcherPaymentService>,
+    tx: &TypedTransaction,
+) -> Option<AccessList> {
+    first_success(provider_set, |payment_service| {
+        payment_service.client().create_access_list(tx, None)
+    })
+    .await
+    .map(|access_list_with_gas_used| access_list_with_gas_used.access_list)
+    .map_err(|e| info!("Node(s) don't support eth_createAccessList, submitting without one: {e}"))
+    .ok()
 }
 
+/// Wraps an error with the gas price that was attempted alongside it.
+#[derive(Debug)]
+pub struct AttemptedGasPriceError<E> {
+    pub source: E,
+    pub attempted_gas_price: U256,
+}
+
+/// `create_new_task_retryable`'s error, carrying the gas price it attempted so a subsequent
+/// `cancel_create_new_task_retryable` call can seed `previous_gas_price` with it.
+pub type CreateTaskError = AttemptedGasPriceError<BatcherError>;
+
+/// Creates a task on-chain at `nonce`, reserved by the caller up front via
+/// `NonceManager::reserve_next` and retried at the same value on `RetryError::Transient`. Always
+/// returns the gas price it attempted, alongside the receipt or the error.
 pub async fn create_new_task_retryable(
     batch_merkle_root: [u8; 32],
     batch_data_pointer: String,
     proofs_submitters: Vec<Address>,
     fee_params: CreateNewTaskFeeParams,
+    access_list: Option<AccessList>,
+    nonce: U256,
     transaction_wait_timeout: u64,
-    payment_service: &BatcherPaymentService,
-    payment_service_fallback: &BatcherPaymentService,
-) -> Result<TransactionReceipt, RetryError<BatcherError>> {
+    provider_set: &ProviderSet<BatcherPaymentService>,
+) -> Result<(TransactionReceipt, U256), RetryError<CreateTaskError>> {
     info!("Creating task for: 0x{}", hex::encode(batch_merkle_root));
-    let call_fallback;
-    let call = payment_service
-        .create_new_task(
-            batch_merkle_root,
-            batch_data_pointer.clone(),
-            proofs_submitters.clone(),
-            fee_params.fee_for_aggregator,
-            fee_params.fee_per_proof,
-            fee_params.respond_to_task_fee_limit,
-        )
-        .gas_price(fee_params.gas_price);
+    // `Ok(None)` is an intentional legacy-pricing fallback; a genuine `Err` must propagate.
+    let fees = match estimate_eip1559_fees_retryable(provider_set, fee_params.priority_fee_floor)
+        .await
+    {
+        Ok(fees) => fees,
+        Err(RetryError::Transient(source)) => {
+            return Err(RetryError::Transient(CreateTaskError {
+                source,
+                attempted_gas_price: fee_params.gas_price,
+            }))
+        }
+        Err(RetryError::Permanent(source)) => {
+            return Err(RetryError::Permanent(CreateTaskError {
+                source,
+                attempted_gas_price: fee_params.gas_price,
+            }))
+        }
+    };
+    let attempted_gas_price = fees
+        .map(|(max_fee_per_gas, _)| max_fee_per_gas)
+        .unwrap_or(fee_params.gas_price);
+
+    let pending_tx = first_contract_success(provider_set, |payment_service| {
+        let mut call = payment_service
+            .create_new_task(
+                batch_merkle_root,
+                batch_data_pointer.clone(),
+                proofs_submitters.clone(),
+                fee_params.fee_for_aggregator,
+                fee_params.fee_per_proof,
+                fee_params.respond_to_task_fee_limit,
+            )
+            .nonce(nonce);
+        apply_fees_and_access_list(&mut call, fees, fee_params.gas_price, access_list.clone());
+        async move { call.send().await }
+    })
+    .await;
 
-    let pending_tx = match call.send().await {
+    let pending_tx = match pending_tx {
         Ok(pending_tx) => pending_tx,
         Err(ContractError::Revert(err)) => {
-            // Since transaction was reverted, we don't want to retry with fallback.
+            // Since transaction was reverted, we don't want to retry with another provider.
             warn!("Transaction reverted {:?}", err);
-            return Err(RetryError::Permanent(BatcherError::TransactionSendError(
-                TransactionSendError::from(err),
-            )));
+            return Err(RetryError::Permanent(CreateTaskError {
+                source: BatcherError::TransactionSendError(TransactionSendError::from(err)),
+                attempted_gas_price,
+            }));
         }
-        _ => {
-            call_fallback = payment_service_fallback
-                .create_new_task(
-                    batch_merkle_root,
-                    batch_data_pointer,
-                    proofs_submitters,
-                    fee_params.fee_for_aggregator,
-                    fee_params.fee_per_proof,
-                    fee_params.respond_to_task_fee_limit,
-                )
-                .gas_price(fee_params.gas_price);
-            match call_fallback.send().await {
-                Ok(pending_tx) => pending_tx,
-                Err(ContractError::Revert(err)) => {
-                    warn!("Transaction reverted {:?}", err);
-                    return Err(RetryError::Permanent(BatcherError::TransactionSendError(
-                        TransactionSendError::from(err),
-                    )));
-                }
-                Err(err) => {
-                    return Err(RetryError::Transient(BatcherError::TransactionSendError(
-                        TransactionSendError::Generic(err.to_string()),
-                    )))
-                }
-            }
+        Err(err) => {
+            return Err(RetryError::Transient(CreateTaskError {
+                source: BatcherError::TransactionSendError(TransactionSendError::Generic(
+                    err.to_string(),
+                )),
+                attempted_gas_price,
+            }))
         }
     };
 
@@ -166,26 +447,41 @@ pub async fn create_new_task_retryable(
         .await
         .map_err(|e| {
             warn!("Error while waiting for batch inclusion: {e}");
-            RetryError::Permanent(BatcherError::ReceiptNotFoundError)
+            RetryError::Permanent(CreateTaskError {
+                source: BatcherError::ReceiptNotFoundError,
+                attempted_gas_price,
+            })
         })?
         .map_err(|e| {
             warn!("Error while waiting for batch inclusion: {e}");
-            RetryError::Permanent(BatcherError::ReceiptNotFoundError)
+            RetryError::Permanent(CreateTaskError {
+                source: BatcherError::ReceiptNotFoundError,
+                attempted_gas_price,
+            })
         })?
-        .ok_or(RetryError::Permanent(BatcherError::ReceiptNotFoundError))
+        .ok_or(RetryError::Permanent(CreateTaskError {
+            source: BatcherError::ReceiptNotFoundError,
+            attempted_gas_price,
+        }))
+        .map(|receipt| (receipt, attempted_gas_price))
 }
 
+/// Prewarms the EIP-2930 access list for `create_new_task`, simulates the call with an `eth_call`
+/// dry run that carries that list, and on success returns the list for the real submission.
 pub async fn simulate_create_new_task_retryable(
     batch_merkle_root: [u8; 32],
     batch_data_pointer: String,
     proofs_submitters: Vec<Address>,
     fee_params: CreateNewTaskFeeParams,
-    payment_service: &BatcherPaymentService,
-    payment_service_fallback: &BatcherPaymentService,
-) -> Result<(), RetryError<BatcherError>> {
+    provider_set: &ProviderSet<BatcherPaymentService>,
+) -> Result<Option<AccessList>, RetryError<BatcherError>> {
     info!("Simulating task for: 0x{}", hex::encode(batch_merkle_root));
-    let simulation_fallback;
-    let simulation = payment_service
+    let fees = estimate_eip1559_fees_retryable(provider_set, fee_params.priority_fee_floor).await?;
+
+    let template_tx = provider_set
+        .providers()
+        .first()
+        .expect("ProviderSet is never empty")
         .create_new_task(
             batch_merkle_root,
             batch_data_pointer.clone(),
@@ -194,94 +490,296 @@ pub async fn simulate_create_new_task_retryable(
             fee_params.fee_per_proof,
             fee_params.respond_to_task_fee_limit,
         )
-        .gas_price(fee_params.gas_price);
-    // sends an `eth_call` request to the node
-    match simulation.call().await {
+        .tx;
+    let access_list = prewarm_access_list_retryable(provider_set, &template_tx).await;
+
+    let result = first_contract_success(provider_set, |payment_service| {
+        let mut simulation = payment_service.create_new_task(
+            batch_merkle_root,
+            batch_data_pointer.clone(),
+            proofs_submitters.clone(),
+            fee_params.fee_for_aggregator,
+            fee_params.fee_per_proof,
+            fee_params.respond_to_task_fee_limit,
+        );
+        apply_fees_and_access_list(
+            &mut simulation,
+            fees,
+            fee_params.gas_price,
+            access_list.clone(),
+        );
+        // sends an `eth_call` request to the node
+        async move { simulation.call().await }
+    })
+    .await;
+
+    match result {
         Ok(_) => {
             info!(
                 "Simulation task for: 0x{} succeeded.",
                 hex::encode(batch_merkle_root)
             );
-            Ok(())
+            Ok(access_list)
         }
         Err(ContractError::Revert(err)) => {
-            // Since transaction was reverted, we don't want to retry with fallback.
+            // Since transaction was reverted, we don't want to retry with another provider.
             warn!("Simulated transaction reverted {:?}", err);
             Err(RetryError::Permanent(BatcherError::TransactionSendError(
                 TransactionSendError::from(err),
             )))
         }
-        _ => {
-            simulation_fallback = payment_service_fallback
-                .create_new_task(
-                    batch_merkle_root,
-                    batch_data_pointer,
-                    proofs_submitters,
-                    fee_params.fee_for_aggregator,
-                    fee_params.fee_per_proof,
-                    fee_params.respond_to_task_fee_limit,
-                )
-                .gas_price(fee_params.gas_price);
-            match simulation_fallback.call().await {
-                Ok(_) => Ok(()),
-                Err(ContractError::Revert(err)) => {
-                    warn!("Simulated transaction reverted {:?}", err);
-                    Err(RetryError::Permanent(BatcherError::TransactionSendError(
-                        TransactionSendError::from(err),
-                    )))
-                }
-                Err(err) => Err(RetryError::Transient(BatcherError::TransactionSendError(
-                    TransactionSendError::Generic(err.to_string()),
-                ))),
-            }
-        }
+        Err(err) => Err(RetryError::Transient(BatcherError::TransactionSendError(
+            TransactionSendError::Generic(err.to_string()),
+        ))),
+    }
+}
+
+/// Minimum bump (numerator/denominator) nodes require to accept a same-nonce replacement tx.
+const MIN_REPLACEMENT_BUMP_NUM: u64 = 112;
+const MIN_REPLACEMENT_BUMP_DEN: u64 = 100;
+
+/// Computes the gas price for the next cancel attempt, bumping by the minimum replacement
+/// percentage and never dropping below what the network currently suggests.
+fn next_replacement_gas_price(previous_gas_price: Option<U256>, network_suggested: U256) -> U256 {
+    match previous_gas_price {
+        Some(previous_gas_price) => (previous_gas_price * MIN_REPLACEMENT_BUMP_NUM
+            / MIN_REPLACEMENT_BUMP_DEN)
+            .max(network_suggested),
+        None => network_suggested,
     }
 }
 
+/// `cancel_create_new_task_retryable`'s error, carrying the gas price it attempted on every
+/// error path, not just a successful broadcast that later times out. When the network gas price
+/// can't be fetched nothing is sent, and it carries `previous_gas_price` (zero if `None`) instead.
+pub type CancelError = AttemptedGasPriceError<ProviderError>;
+
+/// Cancels the batcher's pending task-creation transaction by replacing it with a zero-value
+/// self-transfer at the same `nonce`. `previous_gas_price` should be the last attempted gas
+/// price (from `create_new_task_retryable` or a previous `CancelError`), or `None` on the very
+/// first attempt.
 pub async fn cancel_create_new_task_retryable(
-    batcher_signer: &SignerMiddlewareT,
-    batcher_signer_fallback: &SignerMiddlewareT,
-    bumped_gas_price: U256,
+    signer_provider_set: &ProviderSet<SignerMiddlewareT>,
+    nonce: U256,
+    previous_gas_price: Option<U256>,
     transaction_wait_timeout: u64,
-) -> Result<TransactionReceipt, RetryError<ProviderError>> {
-    let batcher_addr = batcher_signer.address();
+) -> Result<(TransactionReceipt, U256), RetryError<CancelError>> {
+    let batcher_addr = signer_provider_set
+        .providers()
+        .first()
+        .expect("ProviderSet is never empty")
+        .address();
 
-    let current_nonce = get_current_nonce(
-        batcher_signer.provider(),
-        batcher_signer_fallback.provider(),
-        batcher_addr,
-    )
-    .await
-    .map_err(RetryError::Transient)?;
+    let eth_http_providers = ProviderSet::new(
+        signer_provider_set
+            .providers()
+            .iter()
+            .map(|signer| signer.provider().clone())
+            .collect(),
+        signer_provider_set.quorum,
+    );
+
+    let network_suggested = match get_gas_price_retryable(&eth_http_providers).await {
+        Ok(network_suggested) => network_suggested,
+        Err(RetryError::Transient(source)) => {
+            return Err(RetryError::Transient(CancelError {
+                source,
+                attempted_gas_price: previous_gas_price.unwrap_or_default(),
+            }))
+        }
+        Err(RetryError::Permanent(source)) => {
+            return Err(RetryError::Permanent(CancelError {
+                source,
+                attempted_gas_price: previous_gas_price.unwrap_or_default(),
+            }))
+        }
+    };
+    let gas_price = next_replacement_gas_price(previous_gas_price, network_suggested);
 
     let tx = TransactionRequest::new()
         .to(batcher_addr)
         .value(U256::zero())
-        .nonce(current_nonce)
-        .gas_price(bumped_gas_price);
+        .nonce(nonce)
+        .gas_price(gas_price);
 
-    let pending_tx = match batcher_signer.send_transaction(tx.clone(), None).await {
-        Ok(pending_tx) => pending_tx,
-        Err(_) => batcher_signer_fallback
-            .send_transaction(tx.clone(), None)
-            .await
-            .map_err(|e| RetryError::Transient(ProviderError::CustomError(e.to_string())))?,
-    };
+    let pending_tx = first_success(signer_provider_set, |batcher_signer| {
+        batcher_signer.send_transaction(tx.clone(), None)
+    })
+    .await
+    .map_err(|e| {
+        RetryError::Transient(CancelError {
+            source: ProviderError::CustomError(e.to_string()),
+            attempted_gas_price: gas_price,
+        })
+    })?;
 
     // timeout to prevent a deadlock while waiting for the transaction to be included in a block.
     timeout(Duration::from_millis(transaction_wait_timeout), pending_tx)
         .await
         .map_err(|e| {
             warn!("Timeout while waiting for transaction inclusion: {e}");
-            RetryError::Transient(ProviderError::CustomError(format!(
-                "Timeout while waiting for transaction inclusion: {e}"
-            )))
+            RetryError::Transient(CancelError {
+                source: ProviderError::CustomError(format!(
+                    "Timeout while waiting for transaction inclusion: {e}"
+                )),
+                attempted_gas_price: gas_price,
+            })
         })?
         .map_err(|e| {
             warn!("Error while waiting for tx inclusion: {e}");
-            RetryError::Transient(e)
+            RetryError::Transient(CancelError {
+                source: e,
+                attempted_gas_price: gas_price,
+            })
         })?
-        .ok_or(RetryError::Transient(ProviderError::CustomError(
-            "Receipt not found".to_string(),
-        )))
+        .ok_or(RetryError::Transient(CancelError {
+            source: ProviderError::CustomError("Receipt not found".to_string()),
+            attempted_gas_price: gas_price,
+        }))
+        .map(|receipt| (receipt, gas_price))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    use ethers::providers::MockProvider;
+    use ethers::types::Bytes;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn first_contract_success_stops_at_first_revert_without_trying_other_providers() {
+        let call_count = Arc::new(AtomicUsize::new(0));
+        let provider_set = ProviderSet::new(vec![1u8, 2u8], 1);
+
+        let result: Result<u8, ContractError<Provider<MockProvider>>> =
+            first_contract_success(&provider_set, |_| {
+                let call_count = call_count.clone();
+                async move {
+                    call_count.fetch_add(1, Ordering::SeqCst);
+                    Err(ContractError::Revert(Bytes::default()))
+                }
+            })
+            .await;
+
+        assert!(matches!(result, Err(ContractError::Revert(_))));
+        assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn apply_legacy_fee_and_prepare_access_list_promotes_to_type_1_when_access_list_present() {
+        let mut tx = TypedTransaction::default();
+
+        apply_legacy_fee_and_prepare_access_list(&mut tx, U256::from(42), true);
+
+        assert!(matches!(tx, TypedTransaction::Eip2930(_)));
+        assert_eq!(tx.gas_price(), Some(U256::from(42)));
+    }
+
+    #[test]
+    fn apply_legacy_fee_and_prepare_access_list_stays_type_0_when_no_access_list() {
+        let mut tx = TypedTransaction::default();
+
+        apply_legacy_fee_and_prepare_access_list(&mut tx, U256::from(42), false);
+
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+        assert_eq!(tx.gas_price(), Some(U256::from(42)));
+    }
+
+    #[tokio::test]
+    async fn nonce_manager_reserve_next_caches_the_chain_nonce_across_calls() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(7)).unwrap();
+        let provider_set = ProviderSet::new(vec![provider], 1);
+
+        let nonce_manager = NonceManager::new(&provider_set, Address::zero())
+            .await
+            .unwrap();
+
+        // Reserved nonces increase monotonically in memory, with no further RPC round trip — so
+        // a caller that reuses the same reserved value on retry (as create_new_task_retryable and
+        // cancel_create_new_task_retryable require) never collides with the next reservation.
+        assert_eq!(nonce_manager.reserve_next(), U256::from(7));
+        assert_eq!(nonce_manager.reserve_next(), U256::from(8));
+        assert_eq!(nonce_manager.reserve_next(), U256::from(9));
+    }
+
+    #[test]
+    fn next_replacement_gas_price_starts_from_network_suggested_with_no_previous() {
+        assert_eq!(
+            next_replacement_gas_price(None, U256::from(100)),
+            U256::from(100)
+        );
+    }
+
+    #[test]
+    fn next_replacement_gas_price_bumps_by_minimum_percentage() {
+        assert_eq!(
+            next_replacement_gas_price(Some(U256::from(100)), U256::from(100)),
+            U256::from(112)
+        );
+    }
+
+    #[test]
+    fn next_replacement_gas_price_never_drops_below_network_suggested() {
+        assert_eq!(
+            next_replacement_gas_price(Some(U256::from(100)), U256::from(200)),
+            U256::from(200)
+        );
+    }
+
+    #[test]
+    fn median_priority_fee_picks_middle_sample() {
+        let samples = vec![U256::from(10), U256::from(30), U256::from(20)];
+        assert_eq!(median_priority_fee(samples, U256::zero()), U256::from(20));
+    }
+
+    #[test]
+    fn median_priority_fee_clamps_to_floor() {
+        let samples = vec![U256::from(1), U256::from(2), U256::from(3)];
+        assert_eq!(
+            median_priority_fee(samples, U256::from(10)),
+            U256::from(10)
+        );
+    }
+
+    #[test]
+    fn median_priority_fee_falls_back_to_floor_when_no_samples() {
+        assert_eq!(
+            median_priority_fee(Vec::new(), U256::from(5)),
+            U256::from(5)
+        );
+    }
+
+    #[tokio::test]
+    async fn quorum_read_returns_value_once_threshold_of_providers_agree() {
+        let provider_set = ProviderSet::new(vec![1u64, 1u64, 2u64], 2);
+        let result: Result<u64, RetryError<&str>> = quorum_read(
+            &provider_set,
+            |value| {
+                let value = *value;
+                async move { Ok::<u64, &str>(value) }
+            },
+            || "no quorum",
+        )
+        .await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn quorum_read_errors_when_no_value_reaches_threshold() {
+        let provider_set = ProviderSet::new(vec![1u64, 2u64, 3u64], 2);
+        let result: Result<u64, RetryError<&str>> = quorum_read(
+            &provider_set,
+            |value| {
+                let value = *value;
+                async move { Ok::<u64, &str>(value) }
+            },
+            || "no quorum",
+        )
+        .await;
+        assert!(matches!(result, Err(RetryError::Transient("no quorum"))));
+    }
 }